@@ -12,8 +12,9 @@
 //! - Size: The size of a `TypeErased` is not based on the data it contains, but
 //! rather a const generic parameter `C`, effectively a "maximum size" on the types it can contain.
 //!
-//! - Alignment: Until there is a way to define alignment by a const parameter, the alignment of `TypeErased` is
-//! 8 bytes, so anything with an alignment of 8 or less can be contained
+//! - Alignment: The buffer of `TypeErased` is aligned to at least the alignment chosen by its
+//! second generic parameter, an [`Alignment`] marker type ([`Align1`] through [`Align64`]),
+//! defaulting to [`Align8`], so anything with an alignment of `A::ALIGN` or less can be contained
 //!
 //! ## Access
 //! Since there is no type data anymore, any access to the inner data is `unsafe` (except [getting the bytes directly](crate::TypeErased::raw))
@@ -23,30 +24,544 @@
 #[cfg(not(feature = "std"))]
 use core::{
     marker::PhantomData,
-    mem::{align_of, size_of, MaybeUninit},
+    mem::{align_of, size_of, ManuallyDrop, MaybeUninit},
     ptr,
 };
 #[cfg(feature = "std")]
 use std::{
     marker::PhantomData,
-    mem::{align_of, size_of, MaybeUninit},
+    mem::{align_of, size_of, ManuallyDrop, MaybeUninit},
     ptr,
 };
 
+/// Marker for types where every bit pattern of the right size is a valid value, and which have
+/// no padding bytes whose contents matter
+///
+/// This allows [`TypeErased::get_ref`], [`TypeErased::get_mut`], and [`TypeErased::get`] to
+/// reinterpret the erased bytes as `T` without `unsafe`, since any bits sitting in the buffer
+/// are guaranteed to be a valid `T`.
+///
+/// # Safety
+/// Implementors must ensure that:
+/// - Every possible bit pattern of `size_of::<T>()` bytes is a valid instance of `T`
+/// - `T` has no padding bytes (or, if it does, reading uninitialized padding as part of `T` is
+///   not UB for `T`)
+pub unsafe trait Plain {}
+
+macro_rules! impl_plain_for_ints {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            // SAFETY: every bit pattern of `$ty` is a valid `$ty`, and it has no padding
+            unsafe impl Plain for $ty {}
+        )*
+    };
+}
+
+impl_plain_for_ints!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
+
+// SAFETY: every bit pattern of `T` is valid, so every bit pattern of an array of `T` is valid,
+// and arrays have no padding between elements
+unsafe impl<T: Plain, const N: usize> Plain for [T; N] {}
+
+macro_rules! impl_plain_for_tuples {
+    ($($ty:ident),+) => {
+        // SAFETY: every bit pattern of each element type is valid, and this crate does not
+        // support tuples with padding between `Plain` elements
+        unsafe impl<$($ty: Plain),+> Plain for ($($ty,)+) {}
+    };
+}
+
+impl_plain_for_tuples!(A);
+impl_plain_for_tuples!(A, B);
+impl_plain_for_tuples!(A, B, C);
+impl_plain_for_tuples!(A, B, C, D);
+
+// In debug builds, `TypeErased` records the `TypeId` of the `T` it was built from (when `T` is
+// `'static`) so that misuse of `assume_type_ref`/`assume_type_mut`/`assume_type_take` with the
+// wrong `T` can be caught with a `debug_assert!` instead of silently invoking UB. This is
+// entirely absent from release builds, so it costs nothing there.
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Alignment marker used as [`TypeErased`]'s second generic parameter
+///
+/// This is a sealed trait; the only implementors are [`Align1`] through [`Align64`].
+pub trait Alignment: sealed::Sealed + Copy {
+    /// The alignment, in bytes, this marker represents
+    const ALIGN: usize;
+
+    /// A const instance of this marker, used to build one in a `const fn` without relying on
+    /// `Default::default` (which isn't callable in const contexts)
+    const NEW: Self;
+}
+
+macro_rules! define_alignments {
+    ($($(#[$meta:meta])* $name:ident = $n:literal),* $(,)?) => {
+        $(
+            $(#[$meta])*
+            #[repr(align($n))]
+            #[derive(Debug, Default, Clone, Copy)]
+            pub struct $name;
+
+            impl sealed::Sealed for $name {}
+
+            impl Alignment for $name {
+                const ALIGN: usize = $n;
+                const NEW: Self = $name;
+            }
+        )*
+    };
+}
+
+define_alignments!(
+    /// A 1-byte alignment marker
+    Align1 = 1,
+    /// A 2-byte alignment marker
+    Align2 = 2,
+    /// A 4-byte alignment marker
+    Align4 = 4,
+    /// An 8-byte alignment marker; the default used by [`TypeErased`]
+    Align8 = 8,
+    /// A 16-byte alignment marker
+    Align16 = 16,
+    /// A 32-byte alignment marker
+    Align32 = 32,
+    /// A 64-byte alignment marker
+    Align64 = 64,
+);
+
 /// Type-erased data on the stack
 ///
 /// See the [crate-level docs](crate) for more info
-#[repr(C, align(8))]
-pub struct TypeErased<const C: usize> {
+#[repr(C)]
+pub struct TypeErased<const C: usize, A: Alignment = Align8> {
     buf: [MaybeUninit<u8>; C],
+    #[cfg(debug_assertions)]
+    type_id: Option<core::any::TypeId>,
+    // Number of leading bytes of `buf` currently known to hold an initialized value, so that
+    // `get_ref`/`get_mut`/`get` can refuse to read past it instead of exposing uninitialized
+    // bytes as a `Plain` value.
+    init_len: usize,
+    __align: A,
     __no_send_sync: PhantomData<*const ()>,
 }
 
-impl<const C: usize> TypeErased<C> {
+impl<const C: usize, A: Alignment> TypeErased<C, A> {
     /// Creates a new `TypeErased` by erasing the type of `value`
     /// # Panics
+    /// Panics if `size_of::<T>()` is greater than `C` or `align_of::<T>()` is greater than
+    /// `A::ALIGN` (prefer [`new_const`](Self::new_const), which checks this at compile time)
+    #[deprecated(
+        note = "prefer `new_const`, which turns an oversized or over-aligned T into a compile error instead of a runtime panic"
+    )]
+    pub fn new<T: 'static>(value: T) -> Self {
+        assert!(
+            size_of::<T>() <= C,
+            "typeless: size of T ({}) > capacity ({})",
+            size_of::<T>(),
+            C
+        ); // ensure size of C or less
+        assert!(
+            align_of::<T>() <= A::ALIGN,
+            "typeless: alignment of T ({}) > max align ({})",
+            align_of::<T>(),
+            A::ALIGN
+        ); // ensure alignment of A::ALIGN or less
+
+        // SAFETY: the asserts above ensure T fits this TypeErased's size and alignment.
+        unsafe { Self::finish_new(value) }
+    }
+
+    /// Creates a new `TypeErased` by erasing the type of `value`, checking that it fits at
+    /// compile time rather than at runtime
+    /// # Compile-time errors
+    /// Fails to compile (rather than panicking) if `size_of::<T>()` is greater than `C` or
+    /// `align_of::<T>()` is greater than `A::ALIGN`
+    pub fn new_const<T: 'static>(value: T) -> Self {
+        const {
+            assert!(
+                size_of::<T>() <= C,
+                "typeless: size of T > capacity of TypeErased"
+            );
+            assert!(
+                align_of::<T>() <= A::ALIGN,
+                "typeless: alignment of T > max align of TypeErased"
+            );
+        }
+
+        // SAFETY: the const assertions above ensure T fits this TypeErased's size and alignment.
+        unsafe { Self::finish_new(value) }
+    }
+
+    /// Builds a `TypeErased` from an already-checked `T`, additionally recording its `TypeId` in
+    /// debug builds
+    /// # Safety
+    /// `size_of::<T>()` must be less than or equal to `C` and `align_of::<T>()` must be less
+    /// than or equal to `A::ALIGN`.
+    unsafe fn finish_new<T: 'static>(value: T) -> Self {
+        #[allow(unused_mut)]
+        let mut this = Self::new_unchecked(value);
+        #[cfg(debug_assertions)]
+        {
+            this.type_id = Some(core::any::TypeId::of::<T>());
+        }
+        this
+    }
+
+    /// Creates a new `TypeErased` containing no value
+    ///
+    /// This is effectively equivalent to `TypeErased::new::<()>(())`, except that in debug builds
+    /// no `TypeId` is recorded, so [`is::<()>`](Self::is) returns `false`
+    pub const fn empty() -> Self {
+        const U8_UNINIT: MaybeUninit<u8> = MaybeUninit::uninit();
+
+        Self {
+            buf: [U8_UNINIT; C],
+            #[cfg(debug_assertions)]
+            type_id: None,
+            init_len: 0,
+            __align: A::NEW,
+            __no_send_sync: PhantomData,
+        }
+    }
+
+    /// Creates a new `TypeErased` by erasing the type of `value`
+    ///
+    /// Unlike [`new`](Self::new), this does not require `T: 'static`, so in debug builds no
+    /// `TypeId` is recorded for the contained value (it is impossible to obtain one without
+    /// that bound); [`is`](Self::is), [`type_id`](Self::type_id), and the debug-mode checks in
+    /// `assume_type_*` treat it the same as an empty `TypeErased`.
+    /// # Safety
+    /// `size_of::<T>()` must be less than or equal to `C` and `align_of::<T>()` must be less than
+    /// or equal to `A::ALIGN`.
+    ///
+    /// This function will be deprecated once it is possible to ensure these at compile-time.
+    pub unsafe fn new_unchecked<T>(value: T) -> Self {
+        debug_assert!(
+            size_of::<T>() <= C,
+            "typeless: safety requirement violated: size of T ({}) > capacity ({})",
+            size_of::<T>(),
+            C
+        ); // ensure size of C or less
+        debug_assert!(
+            align_of::<T>() <= A::ALIGN,
+            "typeless: safety requirement violated: alignment of T ({}) > max align ({})",
+            align_of::<T>(),
+            A::ALIGN
+        ); // ensure alignment of A::ALIGN or less
+
+        let mut this = Self::empty();
+        let ptr = this.as_mut_ptr::<T>();
+        debug_assert_eq!(
+            ptr as usize % A::ALIGN,
+            0,
+            "typeless: internal error: ptr not aligned to A::ALIGN"
+        ); // ensure pointer alignment of A::ALIGN
+        ptr::write(ptr, value);
+        this.init_len = size_of::<T>();
+        this
+    }
+
+    /// Checks, in debug builds, that the stored `TypeId` (if any) matches `T`
+    /// # Panics
+    /// Panics (in debug builds only) if this contains a recorded `TypeId` for a type other than
+    /// `T`
+    #[cfg(debug_assertions)]
+    fn debug_assert_type<T: 'static>(&self) {
+        if let Some(expected) = self.type_id {
+            debug_assert_eq!(
+                expected,
+                core::any::TypeId::of::<T>(),
+                "typeless: type mismatch: TypeErased does not contain the requested T"
+            );
+        }
+    }
+
+    /// Checks whether this `TypeErased` was constructed from a `T`
+    ///
+    /// Only available in debug builds, where the `TypeId` of the stored type is recorded; this
+    /// returns `false` if `T` is not the exact type used to build this `TypeErased`, and
+    /// returns `false` for values erased through [`new_unchecked`](Self::new_unchecked) since no
+    /// `TypeId` could be recorded for them.
+    #[cfg(debug_assertions)]
+    pub fn is<T: 'static>(&self) -> bool {
+        self.type_id == Some(core::any::TypeId::of::<T>())
+    }
+
+    /// Gets the `TypeId` recorded when this `TypeErased` was constructed, if any
+    ///
+    /// Only available in debug builds. This is `None` if the value was erased through the
+    /// non-`'static` unsafe API, since no `TypeId` could be recorded for it.
+    #[cfg(debug_assertions)]
+    pub fn type_id(&self) -> Option<core::any::TypeId> {
+        self.type_id
+    }
+
+    /// Gets a pointer to some type `T` contained in this `TypeErased`
+    /// # Dereferencability
+    /// The returned pointer is valid to dereference if:
+    /// - The size of `T` is less than or equal to `C`
+    /// - The alignment of `T` is less than or equal to `A::ALIGN`
+    /// - The data in this `TypeErased` is a valid instance of `T` (expired references are not valid)
+    pub const fn as_ptr<T>(&self) -> *const T {
+        self.buf.as_ptr().cast()
+    }
+
+    /// Gets a mutable pointer to some type `T` contained in this `TypeErased`
+    /// # Dereferencability
+    /// The returned pointer is valid to dereference if:
+    /// - The size of `T` is less than or equal to `C`
+    /// - The alignment of `T` is less than or equal to `A::ALIGN`
+    /// - The data in this `TypeErased` is a valid instance of `T` (expired references are not valid)
+    pub fn as_mut_ptr<T>(&mut self) -> *mut T {
+        self.buf.as_mut_ptr().cast()
+    }
+
+    /// Assumes this `TypeErased` contains a valid `T` and returns a reference to it
+    ///
+    /// In debug builds, this `debug_assert!`s that `T`'s `TypeId` matches the one this
+    /// `TypeErased` was constructed with, if any was recorded (see [`is`](Self::is)); this
+    /// requires `T: 'static`. In release builds there is no such check, so `T` is unconstrained,
+    /// preserving the ability to read back values erased from a non-`'static` `T`.
+    /// # Safety
+    /// `size_of::<T>()` must be less than or equal to `C`, `align_of::<T>()` must be less than
+    /// or equal to `A::ALIGN`, and this must contain a valid instance of `T` (expired references are not valid).
+    #[cfg(debug_assertions)]
+    pub unsafe fn assume_type_ref<T: 'static>(&self) -> &T {
+        self.debug_assert_type::<T>();
+
+        &*self.as_ptr()
+    }
+
+    /// Assumes this `TypeErased` contains a valid `T` and returns a reference to it
+    /// # Safety
+    /// `size_of::<T>()` must be less than or equal to `C`, `align_of::<T>()` must be less than
+    /// or equal to `A::ALIGN`, and this must contain a valid instance of `T` (expired references are not valid).
+    #[cfg(not(debug_assertions))]
+    pub unsafe fn assume_type_ref<T>(&self) -> &T {
+        &*self.as_ptr()
+    }
+
+    /// Assumes this `TypeErased` contains a valid `T` and returns a mutable reference to it
+    ///
+    /// In debug builds, this `debug_assert!`s that `T`'s `TypeId` matches the one this
+    /// `TypeErased` was constructed with, if any was recorded (see [`is`](Self::is)); this
+    /// requires `T: 'static`. In release builds there is no such check, so `T` is unconstrained,
+    /// preserving the ability to read back values erased from a non-`'static` `T`.
+    /// # Safety
+    /// `size_of::<T>()` must be less than or equal to `C`, `align_of::<T>()` must be less than
+    /// or equal to `A::ALIGN`, and this must contain a valid instance of `T` (expired references are not valid).
+    #[cfg(debug_assertions)]
+    pub unsafe fn assume_type_mut<T: 'static>(&mut self) -> &mut T {
+        self.debug_assert_type::<T>();
+
+        &mut *self.as_mut_ptr()
+    }
+
+    /// Assumes this `TypeErased` contains a valid `T` and returns a mutable reference to it
+    /// # Safety
+    /// `size_of::<T>()` must be less than or equal to `C`, `align_of::<T>()` must be less than
+    /// or equal to `A::ALIGN`, and this must contain a valid instance of `T` (expired references are not valid).
+    #[cfg(not(debug_assertions))]
+    pub unsafe fn assume_type_mut<T>(&mut self) -> &mut T {
+        &mut *self.as_mut_ptr()
+    }
+
+    /// Assumes this `TypeErased` contains a valid `T` and takes ownership of it
+    ///
+    /// In debug builds, this `debug_assert!`s that `T`'s `TypeId` matches the one this
+    /// `TypeErased` was constructed with, if any was recorded (see [`is`](Self::is)); this
+    /// requires `T: 'static`. In release builds there is no such check, so `T` is unconstrained,
+    /// preserving the ability to read back values erased from a non-`'static` `T`.
+    /// # Safety
+    /// `size_of::<T>()` must be less than or equal to `C`, `align_of::<T>()` must be less than
+    /// or equal to `A::ALIGN`, and this must contain a valid instance of `T` (expired references are not valid).
+    #[cfg(debug_assertions)]
+    pub unsafe fn assume_type_take<T: 'static>(self) -> T {
+        self.debug_assert_type::<T>();
+
+        ptr::read(self.as_ptr())
+    }
+
+    /// Assumes this `TypeErased` contains a valid `T` and takes ownership of it
+    /// # Safety
+    /// `size_of::<T>()` must be less than or equal to `C`, `align_of::<T>()` must be less than
+    /// or equal to `A::ALIGN`, and this must contain a valid instance of `T` (expired references are not valid).
+    #[cfg(not(debug_assertions))]
+    pub unsafe fn assume_type_take<T>(self) -> T {
+        ptr::read(self.as_ptr())
+    }
+
+    /// Assumes this `TypeErased` contains a valid `T`, replaces it with `value`, and returns the
+    /// old one
+    /// # Safety
+    /// `size_of::<T>()` must be less than or equal to `C`, `align_of::<T>()` must be less than
+    /// or equal to `A::ALIGN`, and this must contain a valid instance of `T` (expired references are not valid).
+    pub unsafe fn replace<T>(&mut self, value: T) -> T {
+        let ptr = self.as_mut_ptr::<T>();
+        let old = ptr::read(ptr);
+        ptr::write(ptr, value);
+        old
+    }
+
+    /// Assumes this and `other` both contain a valid `T`, and swaps them
+    /// # Safety
+    /// `size_of::<T>()` must be less than or equal to `C`, `align_of::<T>()` must be less than
+    /// or equal to `A::ALIGN`, both `self` and `other` must contain a valid instance of `T`
+    /// (expired references are not valid), and `self` and `other` must not alias.
+    pub unsafe fn swap<T>(&mut self, other: &mut TypeErased<C, A>) {
+        ptr::swap_nonoverlapping(self.as_mut_ptr::<T>(), other.as_mut_ptr::<T>(), 1);
+    }
+
+    /// Assumes this `TypeErased` contains a valid `T` and takes ownership of it, leaving the
+    /// buffer logically empty
+    /// # Safety
+    /// `size_of::<T>()` must be less than or equal to `C`, `align_of::<T>()` must be less than
+    /// or equal to `A::ALIGN`, and this must contain a valid instance of `T` (expired references are not valid).
+    pub unsafe fn take<T>(&mut self) -> T {
+        let value = ptr::read(self.as_ptr());
+        self.init_len = 0;
+        #[cfg(debug_assertions)]
+        {
+            self.type_id = None;
+        }
+        value
+    }
+
+    /// Gets a reference to the erased bytes reinterpreted as `T`, with no `unsafe` required
+    ///
+    /// Since `T: Plain`, any bit pattern in the buffer is a valid `T`, so this is safe as long
+    /// as `T` fits. This deliberately does not go through the debug-only `TypeId` check used by
+    /// [`assume_type_ref`](Self::assume_type_ref): reinterpreting the same bytes as a different
+    /// `Plain` type is exactly what this method is for.
+    /// # Panics
+    /// Panics if `size_of::<T>()` is greater than `C`, `align_of::<T>()` is greater than
+    /// `A::ALIGN`, or the buffer does not currently hold an initialized value of at least
+    /// `size_of::<T>()` bytes
+    pub fn get_ref<T: Plain>(&self) -> &T {
+        assert!(
+            size_of::<T>() <= C,
+            "typeless: size of T ({}) > capacity ({})",
+            size_of::<T>(),
+            C
+        );
+        assert!(
+            align_of::<T>() <= A::ALIGN,
+            "typeless: alignment of T ({}) > max align ({})",
+            align_of::<T>(),
+            A::ALIGN
+        );
+        assert!(
+            size_of::<T>() <= self.init_len,
+            "typeless: TypeErased does not hold an initialized value of at least size_of::<T>() ({}) bytes",
+            size_of::<T>()
+        );
+
+        // SAFETY: `T: Plain` guarantees any bit pattern is a valid `T`, and the asserts above
+        // ensure `T` fits the buffer's size and alignment and that the buffer is initialized
+        // for at least `size_of::<T>()` bytes.
+        unsafe { &*self.as_ptr() }
+    }
+
+    /// Gets a mutable reference to the erased bytes reinterpreted as `T`, with no `unsafe`
+    /// required
+    ///
+    /// Since `T: Plain`, any bit pattern in the buffer is a valid `T`, so this is safe as long
+    /// as `T` fits. This deliberately does not go through the debug-only `TypeId` check used by
+    /// [`assume_type_mut`](Self::assume_type_mut): reinterpreting the same bytes as a different
+    /// `Plain` type is exactly what this method is for.
+    /// # Panics
+    /// Panics if `size_of::<T>()` is greater than `C`, `align_of::<T>()` is greater than
+    /// `A::ALIGN`, or the buffer does not currently hold an initialized value of at least
+    /// `size_of::<T>()` bytes
+    pub fn get_mut<T: Plain>(&mut self) -> &mut T {
+        assert!(
+            size_of::<T>() <= C,
+            "typeless: size of T ({}) > capacity ({})",
+            size_of::<T>(),
+            C
+        );
+        assert!(
+            align_of::<T>() <= A::ALIGN,
+            "typeless: alignment of T ({}) > max align ({})",
+            align_of::<T>(),
+            A::ALIGN
+        );
+        assert!(
+            size_of::<T>() <= self.init_len,
+            "typeless: TypeErased does not hold an initialized value of at least size_of::<T>() ({}) bytes",
+            size_of::<T>()
+        );
+
+        // SAFETY: `T: Plain` guarantees any bit pattern is a valid `T`, and the asserts above
+        // ensure `T` fits the buffer's size and alignment and that the buffer is initialized
+        // for at least `size_of::<T>()` bytes.
+        unsafe { &mut *self.as_mut_ptr() }
+    }
+
+    /// Copies the erased bytes reinterpreted as `T`, with no `unsafe` required
+    ///
+    /// Since `T: Plain`, any bit pattern in the buffer is a valid `T`, so this is safe as long
+    /// as `T` fits.
+    /// # Panics
+    /// Panics if `size_of::<T>()` is greater than `C`, `align_of::<T>()` is greater than
+    /// `A::ALIGN`, or the buffer does not currently hold an initialized value of at least
+    /// `size_of::<T>()` bytes
+    pub fn get<T: Plain + Copy>(&self) -> T {
+        *self.get_ref()
+    }
+
+    /// Gets the buffer of raw bytes inside
+    pub const fn raw(&self) -> &[MaybeUninit<u8>; C] {
+        &self.buf
+    }
+
+    /// Gets a mutable reference to the buffer of raw bytes inside
+    pub fn raw_mut(&mut self) -> &mut [MaybeUninit<u8>; C] {
+        &mut self.buf
+    }
+
+    /// The maximum size, in bytes, of a value this `TypeErased` can hold
+    pub const MAX_SIZE: usize = C;
+}
+
+/// Checks, at compile time, whether a `T` fits a given `TypeErased`'s size and alignment limits
+///
+/// See [`TypeErased::new_const`] for the constructor that enforces this as a compile-time check.
+pub trait Fits<T> {
+    /// `true` if `T` fits, `false` otherwise
+    const FITS: bool;
+}
+
+impl<const C: usize, A: Alignment, T> Fits<T> for TypeErased<C, A> {
+    const FITS: bool = size_of::<T>() <= C && align_of::<T>() <= A::ALIGN;
+}
+
+/// Type-erased data on the stack that runs the contained value's destructor when dropped
+///
+/// Unlike [`TypeErased`], which behaves like [`mem::forget`](core::mem::forget) on drop,
+/// `DropErased` captures the drop glue of the erased type at construction time, so letting it
+/// go out of scope correctly drops whatever it contains.
+///
+/// See the [crate-level docs](crate) for more info
+#[repr(C, align(8))]
+pub struct DropErased<const C: usize> {
+    buf: [MaybeUninit<u8>; C],
+    drop_fn: Option<unsafe fn(*mut u8)>,
+    // Number of leading bytes of `buf` currently known to hold an initialized value; carried
+    // over by `disarm` so the resulting `TypeErased` knows how much of its buffer is valid.
+    init_len: usize,
+    __no_send_sync: PhantomData<*const ()>,
+}
+
+impl<const C: usize> DropErased<C> {
+    /// Creates a new `DropErased` by erasing the type of `value`
+    /// # Panics
     /// Panics if `size_of::<T>()` is greater than `C` or `align_of::<T>()` is greater than 8
-    /// (eventually these will become compile-time restrictions)
     pub fn new<T: 'static>(value: T) -> Self {
         assert!(
             size_of::<T>() <= C,
@@ -63,24 +578,22 @@ impl<const C: usize> TypeErased<C> {
         unsafe { Self::new_unchecked(value) }
     }
 
-    /// Creates a new `TypeErased` containing no value
-    ///
-    /// This is effectively equivalent to `TypeErased::new::<()>(())`
+    /// Creates a new `DropErased` containing no value
     pub const fn empty() -> Self {
         const U8_UNINIT: MaybeUninit<u8> = MaybeUninit::uninit();
 
         Self {
             buf: [U8_UNINIT; C],
+            drop_fn: None,
+            init_len: 0,
             __no_send_sync: PhantomData,
         }
     }
 
-    /// Creates a new `TypeErased` by erasing the type of `value`
+    /// Creates a new `DropErased` by erasing the type of `value`
     /// # Safety
     /// `size_of::<T>()` must be less than or equal to `C` and `align_of::<T>()` must be less than
     /// or equal to 8.
-    ///
-    /// This function will be deprecated once it is possible to ensure these at compile-time.
     pub unsafe fn new_unchecked<T>(value: T) -> Self {
         debug_assert!(
             size_of::<T>() <= C,
@@ -102,30 +615,36 @@ impl<const C: usize> TypeErased<C> {
             "typeless: internal error: ptr not 8-byte aligned"
         ); // ensure pointer alignment of 8
         ptr::write(ptr, value);
+        this.drop_fn = Some(Self::drop_glue::<T>);
+        this.init_len = size_of::<T>();
         this
     }
 
-    /// Gets a pointer to some type `T` contained in this `TypeErased`
+    unsafe fn drop_glue<T>(ptr: *mut u8) {
+        ptr::drop_in_place(ptr.cast::<T>());
+    }
+
+    /// Gets a pointer to some type `T` contained in this `DropErased`
     /// # Dereferencability
     /// The returned pointer is valid to dereference if:
     /// - The size of `T` is less than or equal to `C`
     /// - The alignment of `T` is less than or equal to 8
-    /// - The data in this `TypeErased` is a valid instance of `T` (expired references are not valid)
+    /// - The data in this `DropErased` is a valid instance of `T` (expired references are not valid)
     pub const fn as_ptr<T>(&self) -> *const T {
         self.buf.as_ptr().cast()
     }
 
-    /// Gets a mutable pointer to some type `T` contained in this `TypeErased`
+    /// Gets a mutable pointer to some type `T` contained in this `DropErased`
     /// # Dereferencability
     /// The returned pointer is valid to dereference if:
     /// - The size of `T` is less than or equal to `C`
     /// - The alignment of `T` is less than or equal to 8
-    /// - The data in this `TypeErased` is a valid instance of `T` (expired references are not valid)
+    /// - The data in this `DropErased` is a valid instance of `T` (expired references are not valid)
     pub fn as_mut_ptr<T>(&mut self) -> *mut T {
         self.buf.as_mut_ptr().cast()
     }
 
-    /// Assumes this `TypeErased` contains a valid `T` and returns a reference to it
+    /// Assumes this `DropErased` contains a valid `T` and returns a reference to it
     /// # Safety
     /// `size_of::<T>()` must be less than or equal to `C`, `align_of::<T>()` must be less than
     /// or equal to 8, and this must contain a valid instance of `T` (expired references are not valid).
@@ -133,7 +652,7 @@ impl<const C: usize> TypeErased<C> {
         &*self.as_ptr()
     }
 
-    /// Assumes this `TypeErased` contains a valid `T` and returns a mutable reference to it
+    /// Assumes this `DropErased` contains a valid `T` and returns a mutable reference to it
     /// # Safety
     /// `size_of::<T>()` must be less than or equal to `C`, `align_of::<T>()` must be less than
     /// or equal to 8, and this must contain a valid instance of `T` (expired references are not valid).
@@ -141,12 +660,30 @@ impl<const C: usize> TypeErased<C> {
         &mut *self.as_mut_ptr()
     }
 
-    /// Assumes this `TypeErased` contains a valid `T` and takes ownership of it
+    /// Disarms this `DropErased`, returning a [`TypeErased`] with the same bytes but without drop
+    /// glue, so the contained value will no longer be dropped automatically
+    pub fn disarm(self) -> TypeErased<C> {
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this` is wrapped in `ManuallyDrop`, so `self`'s `Drop` impl (and thus
+        // `drop_fn`) never runs; we only read the bytes out of it, once, here.
+        TypeErased {
+            buf: unsafe { ptr::read(&this.buf) },
+            #[cfg(debug_assertions)]
+            type_id: None,
+            init_len: this.init_len,
+            __align: Align8,
+            __no_send_sync: PhantomData,
+        }
+    }
+
+    /// Assumes this `DropErased` contains a valid `T` and takes ownership of it, without running
+    /// the stored drop glue on the now-moved-out bytes
     /// # Safety
     /// `size_of::<T>()` must be less than or equal to `C`, `align_of::<T>()` must be less than
     /// or equal to 8, and this must contain a valid instance of `T` (expired references are not valid).
     pub unsafe fn assume_type_take<T>(self) -> T {
-        ptr::read(self.as_ptr())
+        let mut this = ManuallyDrop::new(self);
+        ptr::read(this.as_mut_ptr())
     }
 
     /// Gets the buffer of raw bytes inside
@@ -159,3 +696,13 @@ impl<const C: usize> TypeErased<C> {
         &mut self.buf
     }
 }
+
+impl<const C: usize> Drop for DropErased<C> {
+    fn drop(&mut self) {
+        if let Some(drop_fn) = self.drop_fn {
+            // SAFETY: `drop_fn` is only ever set to drop glue monomorphized for the `T` that was
+            // last written into `buf`, and is cleared whenever that `T` is moved out.
+            unsafe { drop_fn(self.buf.as_mut_ptr().cast()) }
+        }
+    }
+}